@@ -0,0 +1,113 @@
+//! Sembol bazlı, diske kalıcı kapanış fiyatı önbelleği. Alpha Vantage'in
+//! ücretsiz kotası kolayca doluyor; bu önbellek sayesinde uygulama yeniden
+//! başlatıldığında veya sembol değiştirildiğinde grafik, ağ isteği beklemeden
+//! son bilinen verilerle anında çizilir.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CachedSeries {
+    /// Kronolojik sırada (en eski önce), `closes` ile aynı indekslerde tarihler.
+    pub dates: Vec<String>,
+    pub closes: Vec<f64>,
+}
+
+impl CachedSeries {
+    pub fn latest_date(&self) -> Option<&str> {
+        self.dates.last().map(|s| s.as_str())
+    }
+
+    /// Yeni (tarih, kapanış) noktalarını birleştirir; aynı tarih zaten varsa
+    /// üzerine yazar, sonra tarihe göre yeniden sıralar.
+    pub fn merge(&mut self, fresh: &[(String, f64)]) {
+        for (date, close) in fresh {
+            if let Some(pos) = self.dates.iter().position(|d| d == date) {
+                self.closes[pos] = *close;
+            } else {
+                self.dates.push(date.clone());
+                self.closes.push(*close);
+            }
+        }
+        let mut order: Vec<usize> = (0..self.dates.len()).collect();
+        order.sort_by(|&a, &b| self.dates[a].cmp(&self.dates[b]));
+        self.dates = order.iter().map(|&i| self.dates[i].clone()).collect();
+        self.closes = order.iter().map(|&i| self.closes[i]).collect();
+    }
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("fintech-tui").join("cache"))
+}
+
+fn cache_path(symbol: &str) -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join(format!("{}.json", symbol)))
+}
+
+pub fn load(symbol: &str) -> Option<CachedSeries> {
+    let path = cache_path(symbol)?;
+    let raw = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub fn save(symbol: &str, series: &CachedSeries) {
+    let Some(dir) = cache_dir() else { return };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let Some(path) = cache_path(symbol) else {
+        return;
+    };
+    if let Ok(json) = serde_json::to_string_pretty(series) {
+        let _ = fs::write(path, json);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_inserts_new_dates_in_chronological_order() {
+        let mut series = CachedSeries {
+            dates: vec!["2026-01-02".to_string(), "2026-01-03".to_string()],
+            closes: vec![10.0, 11.0],
+        };
+        series.merge(&[
+            ("2026-01-04".to_string(), 12.0),
+            ("2026-01-01".to_string(), 9.0),
+        ]);
+        assert_eq!(
+            series.dates,
+            vec!["2026-01-01", "2026-01-02", "2026-01-03", "2026-01-04"]
+        );
+        assert_eq!(series.closes, vec![9.0, 10.0, 11.0, 12.0]);
+    }
+
+    #[test]
+    fn merge_overwrites_existing_date_instead_of_duplicating() {
+        let mut series = CachedSeries {
+            dates: vec!["2026-01-01".to_string()],
+            closes: vec![9.0],
+        };
+        series.merge(&[("2026-01-01".to_string(), 9.5)]);
+        assert_eq!(series.dates, vec!["2026-01-01"]);
+        assert_eq!(series.closes, vec![9.5]);
+    }
+
+    #[test]
+    fn latest_date_is_the_last_chronological_entry() {
+        let series = CachedSeries {
+            dates: vec!["2026-01-01".to_string(), "2026-01-02".to_string()],
+            closes: vec![1.0, 2.0],
+        };
+        assert_eq!(series.latest_date(), Some("2026-01-02"));
+    }
+
+    #[test]
+    fn latest_date_is_none_when_empty() {
+        assert_eq!(CachedSeries::default().latest_date(), None);
+    }
+}
@@ -0,0 +1,89 @@
+//! Görüntülenen seriyi düz metin muhasebe araçlarına aktarmak için CSV ve
+//! Ledger CLI uyumlu çıktı üretir. Diskteki önbellek yerine ekranda o an
+//! görüntülenen `PriceUpdate` serisinden beslenir; böylece dışa aktarılan
+//! tarihler ve kapanışlar ekranda gösterilenlerle birebir aynıdır.
+
+use std::fs;
+use std::io::{self, Write};
+
+/// Kullanıcının manuel olarak eklediği bir fiyat noktası (ör. bir pozisyon).
+pub struct Position {
+    pub date: String,
+    pub label: String,
+    pub price: f64,
+}
+
+/// `date,close` biçiminde düz bir CSV yazar. `dates` ve `closes`, aynı
+/// indekslerde eşleşen ve ekranda gösterilen seriyle birebir aynı olmalı.
+pub fn export_csv(path: &str, dates: &[String], closes: &[f64]) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    writeln!(file, "date,close")?;
+    for (date, close) in dates.iter().zip(closes.iter()) {
+        writeln!(file, "{},{:.2}", date, close)?;
+    }
+    Ok(())
+}
+
+/// Her fiyat noktasını bir `P DATE SYMBOL $close` fiyat direktifine çevirir.
+pub fn export_ledger(
+    path: &str,
+    symbol: &str,
+    dates: &[String],
+    closes: &[f64],
+    positions: &[Position],
+) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    for (date, close) in dates.iter().zip(closes.iter()) {
+        writeln!(file, "P {} {} ${:.2}", date, symbol, close)?;
+    }
+    for position in positions {
+        writeln!(
+            file,
+            "P {} {} ${:.2}  ; {}",
+            position.date, symbol, position.price, position.label
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(name)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn export_csv_writes_header_then_date_close_rows() {
+        let path = temp_path("fintech_tui_test_export.csv");
+        let dates = vec!["2026-01-01".to_string(), "2026-01-02".to_string()];
+        let closes = vec![10.0, 11.5];
+        export_csv(&path, &dates, &closes).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!(contents, "date,close\n2026-01-01,10.00\n2026-01-02,11.50\n");
+    }
+
+    #[test]
+    fn export_ledger_writes_price_directives_then_positions() {
+        let path = temp_path("fintech_tui_test_export.ledger");
+        let dates = vec!["2026-01-01".to_string()];
+        let closes = vec![10.0];
+        let positions = vec![Position {
+            date: "2026-01-02".to_string(),
+            label: "alım".to_string(),
+            price: 12.0,
+        }];
+        export_ledger(&path, "AAPL", &dates, &closes, &positions).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!(
+            contents,
+            "P 2026-01-01 AAPL $10.00\nP 2026-01-02 AAPL $12.00  ; alım\n"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Kapanış fiyatları üzerinde çalışan saf teknik gösterge fonksiyonları.
+//! Her fonksiyon yetersiz veri durumunda `None` döner.
+
+/// Üstel hareketli ortalamanın tüm seriyi, `k = 2/(n+1)` düzgünleştirme
+/// katsayısıyla takip eder. İlk `period` değerin basit ortalaması tohum
+/// olarak kullanılır; ondan önceki indeksler `None`'dur.
+pub fn ema_series(prices: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; prices.len()];
+    if prices.len() < period || period == 0 {
+        return out;
+    }
+    let k = 2.0 / (period as f64 + 1.0);
+    let seed = prices[..period].iter().sum::<f64>() / period as f64;
+    out[period - 1] = Some(seed);
+    let mut ema = seed;
+    for (i, &price) in prices.iter().enumerate().skip(period) {
+        ema = price * k + ema * (1.0 - k);
+        out[i] = Some(ema);
+    }
+    out
+}
+
+pub fn ema(prices: &[f64], period: usize) -> Option<f64> {
+    ema_series(prices, period).into_iter().flatten().last()
+}
+
+/// Wilder'ın ortalama kazanç/kayıp düzgünleştirmesiyle RSI.
+pub fn rsi(prices: &[f64], period: usize) -> Option<f64> {
+    if prices.len() < period + 1 || period == 0 {
+        return None;
+    }
+    let changes: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+    let mut avg_gain = changes[..period].iter().map(|c| c.max(0.0)).sum::<f64>() / period as f64;
+    let mut avg_loss = changes[..period]
+        .iter()
+        .map(|c| (-c).max(0.0))
+        .sum::<f64>()
+        / period as f64;
+    for &change in &changes[period..] {
+        let gain = change.max(0.0);
+        let loss = (-change).max(0.0);
+        avg_gain = (avg_gain * (period as f64 - 1.0) + gain) / period as f64;
+        avg_loss = (avg_loss * (period as f64 - 1.0) + loss) / period as f64;
+    }
+    if avg_loss == 0.0 {
+        return Some(100.0);
+    }
+    let rs = avg_gain / avg_loss;
+    Some(100.0 - 100.0 / (1.0 + rs))
+}
+
+#[derive(Clone, Copy)]
+pub struct BollingerBands {
+    pub middle: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+/// SMA(n) ± 2 × popülasyon standart sapması.
+pub fn bollinger_bands(prices: &[f64], period: usize) -> Option<BollingerBands> {
+    if prices.len() < period || period == 0 {
+        return None;
+    }
+    let window = &prices[prices.len() - period..];
+    let mean = window.iter().sum::<f64>() / period as f64;
+    let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / period as f64;
+    let stddev = variance.sqrt();
+    Some(BollingerBands {
+        middle: mean,
+        upper: mean + 2.0 * stddev,
+        lower: mean - 2.0 * stddev,
+    })
+}
+
+/// `bollinger_bands`'i her noktada kayan pencereyle tekrarlar, grafik
+/// üzerine bant çizebilmek için; ilk `period - 1` indeks `None`'dur.
+pub fn bollinger_bands_series(prices: &[f64], period: usize) -> Vec<Option<BollingerBands>> {
+    (0..prices.len())
+        .map(|i| bollinger_bands(&prices[..=i], period))
+        .collect()
+}
+
+pub struct Macd {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// MACD = EMA(12) - EMA(26), sinyal hattı MACD'nin 9 periyotluk EMA'sı.
+pub fn macd(prices: &[f64]) -> Option<Macd> {
+    if prices.len() < 26 {
+        return None;
+    }
+    let fast = ema_series(prices, 12);
+    let slow = ema_series(prices, 26);
+    let macd_values: Vec<f64> = fast
+        .iter()
+        .zip(slow.iter())
+        .filter_map(|(f, s)| match (f, s) {
+            (Some(f), Some(s)) => Some(f - s),
+            _ => None,
+        })
+        .collect();
+    if macd_values.len() < 9 {
+        return None;
+    }
+    let signal = ema(&macd_values, 9)?;
+    let macd_now = *macd_values.last()?;
+    Some(Macd {
+        macd: macd_now,
+        signal,
+        histogram: macd_now - signal,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ema_seeds_from_sma_then_recurses() {
+        // seed = SMA(1,2,3) = 2.0, k = 2/(3+1) = 0.5
+        // index 3: 4*0.5 + 2*0.5 = 3.0, index 4: 5*0.5 + 3*0.5 = 4.0
+        let prices = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(ema(&prices, 3), Some(4.0));
+    }
+
+    #[test]
+    fn ema_returns_none_when_not_enough_data() {
+        let prices = [1.0, 2.0];
+        assert_eq!(ema(&prices, 3), None);
+    }
+
+    #[test]
+    fn rsi_is_100_when_all_gains() {
+        let prices = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert_eq!(rsi(&prices, 5), Some(100.0));
+    }
+
+    #[test]
+    fn rsi_is_0_when_all_losses() {
+        let prices = [6.0, 5.0, 4.0, 3.0, 2.0, 1.0];
+        assert_eq!(rsi(&prices, 5), Some(0.0));
+    }
+
+    #[test]
+    fn bollinger_bands_collapse_to_mean_without_volatility() {
+        let prices = [10.0, 10.0, 10.0, 10.0];
+        let bands = bollinger_bands(&prices, 4).unwrap();
+        assert_eq!(bands.middle, 10.0);
+        assert_eq!(bands.upper, 10.0);
+        assert_eq!(bands.lower, 10.0);
+    }
+
+    #[test]
+    fn bollinger_bands_series_is_none_before_period_then_some() {
+        let prices = [10.0, 10.0, 10.0, 10.0];
+        let series = bollinger_bands_series(&prices, 4);
+        assert!(series[0].is_none());
+        assert!(series[1].is_none());
+        assert!(series[2].is_none());
+        assert!(series[3].is_some());
+        assert_eq!(series[3].unwrap().middle, 10.0);
+    }
+
+    #[test]
+    fn macd_none_with_insufficient_history() {
+        let prices = vec![1.0; 20];
+        assert!(macd(&prices).is_none());
+    }
+}
@@ -1,5 +1,4 @@
-use std::collections::BTreeMap;
-use std::io::{self, Write};
+use std::io;
 use std::time::Duration;
 
 use crossterm::{
@@ -11,49 +10,184 @@ use ratatui::{
     Terminal,
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Row, Table},
+    style::{Modifier, Style},
+    widgets::{
+        Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Row, Table,
+        canvas::{Canvas, Line as CanvasLine, Rectangle},
+    },
 };
-use reqwest::blocking::get;
-use serde::Deserialize;
-
-// Alpha Vantage yanıtı için struct
-#[derive(Debug, Deserialize)]
-struct TimeSeriesDaily {
-    #[serde(rename = "Time Series (Daily)")]
-    daily: BTreeMap<String, BTreeMap<String, String>>,
+
+mod cache;
+mod export;
+mod indicators;
+mod providers;
+mod theme;
+mod worker;
+
+use export::Position;
+use providers::{AlphaVantageProvider, BinanceProvider, Candle, PriceProvider};
+use theme::{Resources, Theme};
+use worker::WorkerRequest;
+
+/// Tek bir sembol için taranacak fiyat kaynakları (şimdilik sabit; ileride
+/// yapılandırılabilir hale getirilebilir).
+fn build_providers(api_key: &str) -> Vec<Box<dyn PriceProvider>> {
+    vec![
+        Box::new(AlphaVantageProvider::new(api_key)),
+        Box::new(BinanceProvider),
+    ]
 }
 
-fn fetch_alpha_vantage(symbol: &str, api_key: &str, days: usize) -> Result<Vec<f64>, String> {
-    let url = format!(
-        "https://www.alphavantage.co/query?function=TIME_SERIES_DAILY&symbol={}&apikey={}",
-        symbol, api_key
-    );
-    let resp = get(&url).map_err(|e| format!("HTTP hatası: {}", e))?;
-    let text = resp.text().map_err(|e| format!("Yanıt okunamadı: {}", e))?;
-    let data: TimeSeriesDaily =
-        serde_json::from_str(&text).map_err(|e| format!("JSON hatası: {}", e))?;
-    let mut closes: Vec<(String, f64)> = data
-        .daily
-        .iter()
-        .filter_map(|(date, values)| {
-            values
-                .get("4. close")
-                .and_then(|v| v.parse::<f64>().ok())
-                .map(|close| (date.clone(), close))
-        })
+/// Kullanıcının girdiği sembolü, dosya adı olarak kullanılmaya uygun hale
+/// getirir: yalnızca ASCII harf/rakam, `.` ve `-` kabul edilir (ör. "BRK.B").
+/// Bu karakter kümesi yol ayıracı (`/`, `\`) veya `..` ile dizin dışına
+/// çıkmayı baştan imkansız kılar — sembol hem önbellek dosya adına hem de
+/// dışa aktarma dosya adlarına aynı noktadan, temizlenmiş olarak ulaşır.
+fn sanitize_symbol(input: &str) -> Option<String> {
+    let cleaned: String = input
+        .trim()
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '-')
         .collect();
-    closes.sort_by(|a, b| a.0.cmp(&b.0));
-    let closes: Vec<f64> = closes
-        .into_iter()
-        .rev()
-        .take(days)
-        .map(|(_, v)| v)
-        .collect();
-    if closes.is_empty() {
-        return Err("API'den veri alınamadı".to_string());
+    if cleaned.is_empty() { None } else { Some(cleaned) }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum ChartMode {
+    Line,
+    Candlestick,
+}
+
+/// Alttaki metin girişinin neyi beslediği: yoksa hiçbiri, sembol değişimi
+/// ya da "DATE LABEL PRICE" biçiminde manuel bir pozisyon notu.
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum InputTarget {
+    None,
+    Symbol,
+    Position,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Overlay {
+    None,
+    Ema,
+    Rsi,
+    Bollinger,
+    Macd,
+}
+
+impl Overlay {
+    fn next(self) -> Overlay {
+        match self {
+            Overlay::None => Overlay::Ema,
+            Overlay::Ema => Overlay::Rsi,
+            Overlay::Rsi => Overlay::Bollinger,
+            Overlay::Bollinger => Overlay::Macd,
+            Overlay::Macd => Overlay::None,
+        }
     }
-    Ok(closes.into_iter().rev().collect())
+
+    fn label(self) -> &'static str {
+        match self {
+            Overlay::None => "",
+            Overlay::Ema => "EMA(20)",
+            Overlay::Rsi => "RSI(14)",
+            Overlay::Bollinger => "Bollinger(20)",
+            Overlay::Macd => "MACD(12,26,9)",
+        }
+    }
+}
+
+/// Seçili göstergeyi Bilgi panelinde metin olarak özetler.
+fn overlay_text(overlay: Overlay, prices: &[f64]) -> String {
+    match overlay {
+        Overlay::None => String::new(),
+        Overlay::Ema => match indicators::ema(prices, 20) {
+            Some(v) => format!("EMA(20): {:.2}", v),
+            None => "EMA(20) için yeterli veri yok.".to_string(),
+        },
+        Overlay::Rsi => match indicators::rsi(prices, 14) {
+            Some(v) => format!("RSI(14): {:.2}", v),
+            None => "RSI(14) için yeterli veri yok.".to_string(),
+        },
+        Overlay::Bollinger => match indicators::bollinger_bands(prices, 20) {
+            Some(b) => format!(
+                "Bollinger(20): alt {:.2} / orta {:.2} / üst {:.2}",
+                b.lower, b.middle, b.upper
+            ),
+            None => "Bollinger(20) için yeterli veri yok.".to_string(),
+        },
+        Overlay::Macd => match indicators::macd(prices) {
+            Some(m) => format!(
+                "MACD: {:.2} / sinyal {:.2} / histogram {:.2}",
+                m.macd, m.signal, m.histogram
+            ),
+            None => "MACD için yeterli veri yok.".to_string(),
+        },
+    }
+}
+
+/// Mum grafiğini bir `Canvas` üzerine çizer: her gün için yüksek-düşük
+/// aralığını gösteren dikey bir fitil ve açılış/kapanışı gösteren renkli bir
+/// gövde (kapanış >= açılış ise yeşil, aksi halde kırmızı).
+fn render_candlestick_chart(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    candles: &[Candle],
+    theme: &Theme,
+) {
+    let low = candles
+        .iter()
+        .map(|c| c.low)
+        .fold(f64::INFINITY, f64::min);
+    let high = candles
+        .iter()
+        .map(|c| c.high)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let (y_min, y_max) = if (high - low).abs() < std::f64::EPSILON {
+        (low - 1.0, high + 1.0)
+    } else {
+        (low.floor(), high.ceil())
+    };
+    let n = candles.len().max(1) as f64;
+
+    let canvas = Canvas::default()
+        .block(
+            Block::default()
+                .title("Mum Grafiği (Açılış-Yüksek-Düşük-Kapanış)")
+                .borders(Borders::ALL),
+        )
+        .x_bounds([0.0, n])
+        .y_bounds([y_min, y_max])
+        .paint(move |ctx| {
+            for (i, candle) in candles.iter().enumerate() {
+                let x = i as f64 + 0.5;
+                let up = candle.close >= candle.open;
+                let color = if up { theme.candle_up } else { theme.candle_down };
+
+                // Fitil: günün yüksek-düşük aralığı.
+                ctx.draw(&CanvasLine {
+                    x1: x,
+                    y1: candle.low,
+                    x2: x,
+                    y2: candle.high,
+                    color,
+                });
+
+                // Gövde: açılış-kapanış aralığı.
+                let body_top = candle.open.max(candle.close);
+                let body_bottom = candle.open.min(candle.close);
+                ctx.draw(&Rectangle {
+                    x: x - 0.3,
+                    y: body_bottom,
+                    width: 0.6,
+                    height: (body_top - body_bottom).max(0.01),
+                    color,
+                });
+            }
+        });
+    f.render_widget(canvas, area);
 }
 
 // Hareketli ortalama
@@ -67,7 +201,8 @@ mod ml_fin {
     }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -75,11 +210,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     let api_key = "XOTA84CVGZ6QL713";
-    let mut symbol = String::from("AAPL");
-    let mut prices: Vec<f64> = fetch_alpha_vantage(&symbol, api_key, 30).unwrap_or_default();
-    let mut error_msg = String::new();
+    let initial_symbol = String::from("AAPL");
+    let providers = build_providers(api_key);
+    let (updates, requests) = worker::spawn(providers, initial_symbol, 30);
+
+    // Sembol değiştirmek artık alternatif ekrandan çıkmayı gerektirmiyor;
+    // Enter ile bir giriş kipine girilip sembol burada, TUI içinde yazılıyor.
+    let mut input_target = InputTarget::None;
+    let mut input_buffer = String::new();
+    let mut chart_mode = ChartMode::Line;
+    let mut overlay = Overlay::None;
+    let mut positions: Vec<Position> = Vec::new();
+    let mut status_msg = String::new();
+    let mut resources = Resources::new();
 
     loop {
+        let update = updates.borrow().clone();
+        let theme = resources.theme();
+
         terminal.draw(|f| {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -92,17 +240,43 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 ])
                 .split(f.size());
 
+            let title = if input_target == InputTarget::Symbol {
+                format!(" Yeni sembol: {}_ ", input_buffer)
+            } else if input_target == InputTarget::Position {
+                format!(" Pozisyon (TARİH ETİKET FİYAT): {}_ ", input_buffer)
+            } else if update.refreshing && update.from_cache {
+                format!(
+                    " Alpha Vantage Terminal - Sembol: {} (önbellekten, güncelleniyor…) ",
+                    update.symbol
+                )
+            } else if update.refreshing {
+                format!(
+                    " Alpha Vantage Terminal - Sembol: {} (yenileniyor…) ",
+                    update.symbol
+                )
+            } else {
+                let overlay_label = overlay.label();
+                format!(
+                    " Alpha Vantage Terminal - Sembol: {} (Q: çık, C: mum/çizgi, I: gösterge, P: pozisyon, E: dışa aktar, T: tema [{}]{}) ",
+                    update.symbol,
+                    theme.name,
+                    if overlay_label.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" [{}]", overlay_label)
+                    }
+                )
+            };
             let block = Block::default()
-                .title(format!(
-                    " Alpha Vantage Terminal - Sembol: {} (Çıkmak için Q) ",
-                    symbol
-                ))
+                .title(title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan));
+                .border_style(Style::default().fg(theme.border));
             f.render_widget(block, chunks[0]);
 
+            let prices = &update.prices;
             if prices.is_empty() {
                 let info = Paragraph::new("Veri bulunamadı veya API'den fiyat alınamadı.")
+                    .style(Style::default().fg(theme.error_text))
                     .block(Block::default().title("Uyarı").borders(Borders::ALL));
                 f.render_widget(&info, chunks[1]);
                 f.render_widget(&info, chunks[2]);
@@ -120,52 +294,117 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 );
                 f.render_widget(table, chunks[1]);
 
-                let chart_prices: Vec<(f64, f64)> = prices
-                    .iter()
-                    .enumerate()
-                    .map(|(i, v)| (i as f64, *v))
-                    .collect();
-
-                let (y_min, y_max) = {
-                    let min = prices.iter().cloned().fold(f64::INFINITY, f64::min);
-                    let max = prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-                    if (max - min).abs() < std::f64::EPSILON {
-                        (min - 1.0, max + 1.0)
+                if chart_mode == ChartMode::Candlestick && !update.candles.is_empty() {
+                    render_candlestick_chart(f, chunks[2], &update.candles, theme);
+                } else {
+                    let chart_prices: Vec<(f64, f64)> = prices
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| (i as f64, *v))
+                        .collect();
+
+                    let (y_min, y_max) = {
+                        let min = prices.iter().cloned().fold(f64::INFINITY, f64::min);
+                        let max = prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                        if (max - min).abs() < std::f64::EPSILON {
+                            (min - 1.0, max + 1.0)
+                        } else {
+                            (min.floor(), max.ceil())
+                        }
+                    };
+
+                    let ema_points: Vec<(f64, f64)> = if overlay == Overlay::Ema {
+                        indicators::ema_series(prices, 20)
+                            .into_iter()
+                            .enumerate()
+                            .filter_map(|(i, v)| v.map(|v| (i as f64, v)))
+                            .collect()
                     } else {
-                        (min.floor(), max.ceil())
+                        Vec::new()
+                    };
+
+                    let (bollinger_upper, bollinger_middle, bollinger_lower) =
+                        if overlay == Overlay::Bollinger {
+                            let bands = indicators::bollinger_bands_series(prices, 20);
+                            let extract = |pick: fn(&indicators::BollingerBands) -> f64| {
+                                bands
+                                    .iter()
+                                    .enumerate()
+                                    .filter_map(|(i, b)| b.map(|b| (i as f64, pick(&b))))
+                                    .collect::<Vec<(f64, f64)>>()
+                            };
+                            (
+                                extract(|b| b.upper),
+                                extract(|b| b.middle),
+                                extract(|b| b.lower),
+                            )
+                        } else {
+                            (Vec::new(), Vec::new(), Vec::new())
+                        };
+
+                    let mut datasets = vec![
+                        Dataset::default()
+                            .name("Kapanış")
+                            .graph_type(GraphType::Line) // Çizgi grafik!
+                            .style(Style::default().fg(theme.chart_line))
+                            .data(&chart_prices),
+                    ];
+                    if !ema_points.is_empty() {
+                        datasets.push(
+                            Dataset::default()
+                                .name("EMA(20)")
+                                .graph_type(GraphType::Line)
+                                .style(Style::default().fg(theme.overlay_line))
+                                .data(&ema_points),
+                        );
                     }
-                };
-
-                let datasets = vec![
-                    Dataset::default()
-                        .name("Kapanış")
-                        .graph_type(GraphType::Line) // Çizgi grafik!
-                        .style(Style::default().fg(Color::Yellow))
-                        .data(&chart_prices),
-                ];
-                let chart = Chart::new(datasets)
-                    .block(
-                        Block::default()
-                            .title("Son 30 Günlük Kapanış Fiyatı")
-                            .borders(Borders::ALL),
-                    )
-                    .x_axis(
-                        Axis::default()
-                            .title("Gün")
-                            .style(Style::default().fg(Color::Gray))
-                            .bounds([0.0, chart_prices.len().max(1) as f64]),
-                    )
-                    .y_axis(
-                        Axis::default()
-                            .title("Fiyat")
-                            .style(Style::default().fg(Color::Gray))
-                            .bounds([y_min, y_max]),
-                    );
-                f.render_widget(chart, chunks[2]);
+                    if !bollinger_upper.is_empty() {
+                        datasets.push(
+                            Dataset::default()
+                                .name("Bollinger Üst")
+                                .graph_type(GraphType::Line)
+                                .style(Style::default().fg(theme.overlay_line))
+                                .data(&bollinger_upper),
+                        );
+                        datasets.push(
+                            Dataset::default()
+                                .name("Bollinger Orta")
+                                .graph_type(GraphType::Line)
+                                .style(Style::default().fg(theme.overlay_line))
+                                .data(&bollinger_middle),
+                        );
+                        datasets.push(
+                            Dataset::default()
+                                .name("Bollinger Alt")
+                                .graph_type(GraphType::Line)
+                                .style(Style::default().fg(theme.overlay_line))
+                                .data(&bollinger_lower),
+                        );
+                    }
+                    let chart = Chart::new(datasets)
+                        .block(
+                            Block::default()
+                                .title("Günlük Kapanış Fiyatı Geçmişi")
+                                .borders(Borders::ALL),
+                        )
+                        .x_axis(
+                            Axis::default()
+                                .title("Gün")
+                                .style(Style::default().fg(theme.axis))
+                                .bounds([0.0, chart_prices.len().max(1) as f64]),
+                        )
+                        .y_axis(
+                            Axis::default()
+                                .title("Fiyat")
+                                .style(Style::default().fg(theme.axis))
+                                .bounds([y_min, y_max]),
+                        );
+                    f.render_widget(chart, chunks[2]);
+                }
             }
 
             let ma_text = if !prices.is_empty() {
-                if let Some(ma) = ml_fin::moving_average(&prices, 5) {
+                if let Some(ma) = ml_fin::moving_average(prices, 5) {
                     format!("Son 5 fiyatın hareketli ortalaması: {:.2}", ma)
                 } else {
                     "Hareketli ortalama için yeterli veri yok.".to_string()
@@ -173,50 +412,135 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             } else {
                 "".to_string()
             };
-            let error = if !error_msg.is_empty() {
-                format!("Hata: {}", error_msg)
+            let error = update
+                .error
+                .as_ref()
+                .map(|e| format!("Hata: {}", e))
+                .unwrap_or_default();
+            let agreement = if update.sources_total > 0 {
+                format!("{}/{} kaynak anlaştı", update.sources_agreed, update.sources_total)
             } else {
                 "".to_string()
             };
-            let info = format!("{}   {}", ma_text, error);
+            let overlay_info = overlay_text(overlay, prices);
+            let info = format!(
+                "{}   {}   {}   {}   {}",
+                ma_text, agreement, overlay_info, status_msg, error
+            );
             let info_block = Block::default().borders(Borders::ALL).title("Bilgi");
-            f.render_widget(Paragraph::new(info).block(info_block), chunks[3]);
+            let info_style = if update.error.is_some() {
+                Style::default().fg(theme.error_text)
+            } else {
+                Style::default()
+            };
+            f.render_widget(
+                Paragraph::new(info).style(info_style).block(info_block),
+                chunks[3],
+            );
         })?;
 
         if event::poll(Duration::from_millis(1500))? {
             if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Char('Q') => break,
-                    KeyCode::Enter => {
-                        disable_raw_mode()?;
-                        execute!(io::stdout(), LeaveAlternateScreen)?;
-                        print!("Yeni sembol girin: ");
-                        io::stdout().flush().unwrap();
-                        let mut new_symbol = String::new();
-                        io::stdin().read_line(&mut new_symbol)?;
-                        let new_symbol = new_symbol.trim().to_uppercase();
-                        if !new_symbol.is_empty() {
-                            let new_prices = fetch_alpha_vantage(&new_symbol, api_key, 30);
-                            match new_prices {
-                                Ok(p) if !p.is_empty() => {
-                                    symbol = new_symbol;
-                                    prices = p;
-                                    error_msg.clear();
+                match input_target {
+                    InputTarget::Symbol => match key.code {
+                        KeyCode::Enter => {
+                            match sanitize_symbol(&input_buffer) {
+                                Some(new_symbol) => {
+                                    requests.send(WorkerRequest::ChangeSymbol(new_symbol)).ok();
                                 }
-                                Ok(_) => {
-                                    error_msg = "Bu sembol için veri bulunamadı.".to_string();
-                                    // Eski fiyatlar korunur, grafik kaybolmaz
+                                None => {
+                                    status_msg = "Geçersiz sembol (harf/rakam, '.', '-').".to_string();
                                 }
-                                Err(e) => {
-                                    error_msg = e;
-                                    // Eski fiyatlar korunur, grafik kaybolmaz
+                            }
+                            input_buffer.clear();
+                            input_target = InputTarget::None;
+                        }
+                        KeyCode::Esc => {
+                            input_buffer.clear();
+                            input_target = InputTarget::None;
+                        }
+                        KeyCode::Backspace => {
+                            input_buffer.pop();
+                        }
+                        KeyCode::Char(c) => input_buffer.push(c),
+                        _ => {}
+                    },
+                    InputTarget::Position => match key.code {
+                        KeyCode::Enter => {
+                            let parts: Vec<&str> = input_buffer.trim().splitn(3, ' ').collect();
+                            match parts.as_slice() {
+                                [date, label, price] => match price.parse::<f64>() {
+                                    Ok(price) => {
+                                        positions.push(Position {
+                                            date: date.to_string(),
+                                            label: label.to_string(),
+                                            price,
+                                        });
+                                        status_msg = format!("Pozisyon eklendi: {} {}", label, price);
+                                    }
+                                    Err(_) => {
+                                        status_msg = "Fiyat sayı olmalı.".to_string();
+                                    }
+                                },
+                                _ => {
+                                    status_msg = "Biçim: TARİH ETİKET FİYAT".to_string();
                                 }
                             }
+                            input_buffer.clear();
+                            input_target = InputTarget::None;
                         }
-                        enable_raw_mode()?;
-                        execute!(io::stdout(), EnterAlternateScreen)?;
-                    }
-                    _ => {}
+                        KeyCode::Esc => {
+                            input_buffer.clear();
+                            input_target = InputTarget::None;
+                        }
+                        KeyCode::Backspace => {
+                            input_buffer.pop();
+                        }
+                        KeyCode::Char(c) => input_buffer.push(c),
+                        _ => {}
+                    },
+                    InputTarget::None => match key.code {
+                        KeyCode::Char('q') | KeyCode::Char('Q') => break,
+                        KeyCode::Enter => input_target = InputTarget::Symbol,
+                        KeyCode::Char('r') | KeyCode::Char('R') => {
+                            requests.send(WorkerRequest::Refresh).ok();
+                        }
+                        KeyCode::Char('c') | KeyCode::Char('C') => {
+                            chart_mode = match chart_mode {
+                                ChartMode::Line => ChartMode::Candlestick,
+                                ChartMode::Candlestick => ChartMode::Line,
+                            };
+                        }
+                        KeyCode::Char('i') | KeyCode::Char('I') => {
+                            overlay = overlay.next();
+                        }
+                        KeyCode::Char('p') | KeyCode::Char('P') => {
+                            input_target = InputTarget::Position;
+                        }
+                        KeyCode::Char('t') | KeyCode::Char('T') => {
+                            resources.next_theme();
+                        }
+                        KeyCode::Char('e') | KeyCode::Char('E') => {
+                            let symbol = &update.symbol;
+                            let csv_path = format!("{}_export.csv", symbol);
+                            let ledger_path = format!("{}_export.ledger", symbol);
+                            let result = export::export_csv(&csv_path, &update.dates, &update.prices)
+                                .and_then(|_| {
+                                    export::export_ledger(
+                                        &ledger_path,
+                                        symbol,
+                                        &update.dates,
+                                        &update.prices,
+                                        &positions,
+                                    )
+                                });
+                            status_msg = match result {
+                                Ok(()) => format!("Dışa aktarıldı: {} / {}", csv_path, ledger_path),
+                                Err(e) => format!("Dışa aktarma hatası: {}", e),
+                            };
+                        }
+                        _ => {}
+                    },
                 }
             }
         }
@@ -0,0 +1,370 @@
+use std::collections::BTreeMap;
+
+use reqwest::blocking::get;
+use serde::Deserialize;
+
+/// Bir fiyat kaynağı: sembol için son `days` günün tam OHLC serisini,
+/// tarihleriyle birlikte tek bir ağ çağrısında döner (kronolojik, en eski
+/// önce). Kapanış serisi, mum grafiği ve tarihli önbellek ihtiyaçlarının
+/// hepsi bu tek sonuçtan türetilir — aksi halde aynı uç noktaya sembol
+/// başına birden çok istek atılırdı. `Send + Sync` şartı, arka plan
+/// worker'ının sağlayıcıları bir tokio görevine taşıyabilmesi için gerekli.
+pub trait PriceProvider: Send + Sync {
+    fn name(&self) -> &str;
+    fn fetch_daily(&self, symbol: &str, days: usize) -> Result<Vec<(String, Candle)>, String>;
+}
+
+/// Bir günün açılış/en yüksek/en düşük/kapanış fiyatları.
+#[derive(Debug, Clone, Copy)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeSeriesDaily {
+    #[serde(rename = "Time Series (Daily)")]
+    daily: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+pub struct AlphaVantageProvider {
+    api_key: String,
+}
+
+impl AlphaVantageProvider {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+        }
+    }
+}
+
+impl PriceProvider for AlphaVantageProvider {
+    fn name(&self) -> &str {
+        "Alpha Vantage"
+    }
+
+    fn fetch_daily(&self, symbol: &str, days: usize) -> Result<Vec<(String, Candle)>, String> {
+        let url = format!(
+            "https://www.alphavantage.co/query?function=TIME_SERIES_DAILY&symbol={}&apikey={}",
+            symbol, self.api_key
+        );
+        let resp = get(&url).map_err(|e| format!("HTTP hatası: {}", e))?;
+        let text = resp.text().map_err(|e| format!("Yanıt okunamadı: {}", e))?;
+        let data: TimeSeriesDaily =
+            serde_json::from_str(&text).map_err(|e| format!("JSON hatası: {}", e))?;
+        let mut candles: Vec<(String, Candle)> = data
+            .daily
+            .iter()
+            .filter_map(|(date, values)| {
+                let open = values.get("1. open")?.parse::<f64>().ok()?;
+                let high = values.get("2. high")?.parse::<f64>().ok()?;
+                let low = values.get("3. low")?.parse::<f64>().ok()?;
+                let close = values.get("4. close")?.parse::<f64>().ok()?;
+                Some((
+                    date.clone(),
+                    Candle {
+                        open,
+                        high,
+                        low,
+                        close,
+                    },
+                ))
+            })
+            .collect();
+        candles.sort_by(|a, b| a.0.cmp(&b.0));
+        let candles: Vec<(String, Candle)> = candles.into_iter().rev().take(days).collect();
+        if candles.is_empty() {
+            return Err("API'den veri alınamadı".to_string());
+        }
+        Ok(candles.into_iter().rev().collect())
+    }
+}
+
+/// Binance klines (mum verisi) uç noktasından OHLC okur. `symbol` doğrudan
+/// Binance çift adı olarak kullanılır (ör. "BTCUSDT").
+pub struct BinanceProvider;
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct Klines {
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+impl PriceProvider for BinanceProvider {
+    fn name(&self) -> &str {
+        "Binance"
+    }
+
+    fn fetch_daily(&self, symbol: &str, days: usize) -> Result<Vec<(String, Candle)>, String> {
+        let url = format!(
+            "https://api.binance.com/api/v3/klines?symbol={}&interval=1d&limit={}",
+            symbol, days
+        );
+        let resp = get(&url).map_err(|e| format!("HTTP hatası: {}", e))?;
+        let text = resp.text().map_err(|e| format!("Yanıt okunamadı: {}", e))?;
+        let klines: Klines =
+            serde_json::from_str(&text).map_err(|e| format!("JSON hatası: {}", e))?;
+        // Sütunlar: [0]=open time (ms), [1]=open, [2]=high, [3]=low, [4]=close, ...
+        let candles: Vec<(String, Candle)> = klines
+            .rows
+            .iter()
+            .filter_map(|row| {
+                let open_time_ms = row.first()?.as_i64()?;
+                let date = epoch_millis_to_date(open_time_ms);
+                let open = row.get(1)?.as_str()?.parse::<f64>().ok()?;
+                let high = row.get(2)?.as_str()?.parse::<f64>().ok()?;
+                let low = row.get(3)?.as_str()?.parse::<f64>().ok()?;
+                let close = row.get(4)?.as_str()?.parse::<f64>().ok()?;
+                Some((
+                    date,
+                    Candle {
+                        open,
+                        high,
+                        low,
+                        close,
+                    },
+                ))
+            })
+            .collect();
+        if candles.is_empty() {
+            return Err("Binance'den veri alınamadı".to_string());
+        }
+        Ok(candles)
+    }
+}
+
+/// Binance'in `openTime`'ı epoch milisaniye olarak döner; bunu Alpha
+/// Vantage'in tarihleriyle aynı şekilde karşılaştırabilmek için `YYYY-MM-DD`
+/// biçimine çevirir (takvim hesapları UTC gün sınırı varsayar).
+fn epoch_millis_to_date(epoch_millis: i64) -> String {
+    let days_since_epoch = epoch_millis.div_euclid(86_400_000);
+    let (year, month, day) = civil_from_days(days_since_epoch);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Howard Hinnant'ın `civil_from_days` algoritması: 1970-01-01'den bu yana
+/// geçen gün sayısını proleptik Gregoryen takvim tarihine çevirir.
+fn civil_from_days(days_since_epoch: i64) -> (i64, u32, u32) {
+    let z = days_since_epoch + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let year = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if month <= 2 { year + 1 } else { year };
+    (year, month, day)
+}
+
+/// Bugünün tarihini `YYYY-MM-DD` biçiminde döner (UTC gün sınırı varsayar);
+/// worker bunu önbelleğin tazeliğini kontrol etmek için kullanır.
+pub fn today_date() -> String {
+    let epoch_millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    epoch_millis_to_date(epoch_millis)
+}
+
+/// Sağlayıcıları sırayla dener ve boş olmayan günlük OHLC serisi döndüren
+/// ilkinin sonucunu döner.
+pub fn primary_daily(
+    providers: &[Box<dyn PriceProvider>],
+    symbol: &str,
+    days: usize,
+) -> Result<Vec<(String, Candle)>, String> {
+    for provider in providers {
+        if let Ok(daily) = provider.fetch_daily(symbol, days) {
+            if !daily.is_empty() {
+                return Ok(daily);
+            }
+        }
+    }
+    Err("Hiçbir kaynaktan günlük veri alınamadı".to_string())
+}
+
+/// Tüm sağlayıcılardan günlük OHLC serilerini çeker ve boş olmayanları toplar
+/// (sembol başına sağlayıcı sayısı kadar ağ çağrısı; tekrar yok).
+pub fn fetch_all_daily(
+    providers: &[Box<dyn PriceProvider>],
+    symbol: &str,
+    days: usize,
+) -> Vec<Vec<(String, Candle)>> {
+    providers
+        .iter()
+        .filter_map(|p| p.fetch_daily(symbol, days).ok())
+        .filter(|daily| !daily.is_empty())
+        .collect()
+}
+
+pub struct AggregateResult {
+    /// `closes` ile aynı indekslerde, kronolojik sırada tarihler.
+    pub dates: Vec<String>,
+    pub closes: Vec<f64>,
+    /// Son gün için kaç kaynağın anlaştığı (medyandan sapmayan kaynak sayısı).
+    pub sources_agreed: usize,
+    pub sources_total: usize,
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Bir günün tüm kaynaklardan gelen kapanış değerlerini medyanla uzlaştırır:
+/// medyandan `outlier_fraction` oranından fazla sapanlar elenir, hayatta
+/// kalanların medyanı döner. Döndürülen ikincisi, hayatta kalan kaynak sayısı.
+fn reconcile_day(values: &[f64], outlier_fraction: f64) -> (f64, usize) {
+    let day_median = median(values);
+    let survivors: Vec<f64> = values
+        .iter()
+        .copied()
+        .filter(|v| day_median == 0.0 || (v - day_median).abs() / day_median <= outlier_fraction)
+        .collect();
+    let final_value = if survivors.is_empty() {
+        day_median
+    } else {
+        median(&survivors)
+    };
+    (final_value, survivors.len())
+}
+
+/// Önceden çekilmiş sağlayıcı başına günlük serileri **tarihe göre** hizalar
+/// (sondan index'e göre değil — borsalar hafta sonu/tatil kapalıyken kripto
+/// işlem görmeye devam eder, bu yüzden sondan hizalama aynı takvim gününü
+/// karşılaştırmayı garanti etmez) ve gün gün medyanını alır.
+pub fn aggregate_closes(
+    per_provider: &[Vec<(String, Candle)>],
+    outlier_fraction: f64,
+) -> Result<AggregateResult, String> {
+    if per_provider.is_empty() {
+        return Err("Hiçbir kaynaktan veri alınamadı".to_string());
+    }
+
+    let mut by_date: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for series in per_provider {
+        for (date, candle) in series {
+            by_date.entry(date.clone()).or_default().push(candle.close);
+        }
+    }
+
+    let mut dates = Vec::with_capacity(by_date.len());
+    let mut closes = Vec::with_capacity(by_date.len());
+    let mut sources_agreed = 0usize;
+    let mut sources_total = 0usize;
+    let last_date = by_date.keys().next_back().cloned();
+
+    for (date, values) in &by_date {
+        let (final_value, agreed) = reconcile_day(values, outlier_fraction);
+        dates.push(date.clone());
+        closes.push(final_value);
+        if Some(date) == last_date.as_ref() {
+            sources_agreed = agreed;
+            sources_total = values.len();
+        }
+    }
+
+    Ok(AggregateResult {
+        dates,
+        closes,
+        sources_agreed,
+        sources_total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_of_odd_and_even_length() {
+        assert_eq!(median(&[1.0, 2.0, 3.0]), 2.0);
+        assert_eq!(median(&[1.0, 2.0, 3.0, 4.0]), 2.5);
+    }
+
+    #[test]
+    fn reconcile_day_drops_outlier_beyond_fraction() {
+        let (value, agreed) = reconcile_day(&[100.0, 101.0, 150.0], 0.10);
+        assert_eq!(agreed, 2);
+        assert_eq!(value, 100.5);
+    }
+
+    #[test]
+    fn reconcile_day_keeps_all_within_fraction() {
+        let (value, agreed) = reconcile_day(&[100.0, 101.0, 102.0], 0.10);
+        assert_eq!(agreed, 3);
+        assert_eq!(value, 101.0);
+    }
+
+    #[test]
+    fn aggregate_closes_aligns_by_date_not_trailing_index() {
+        // AV (hisse) hafta sonu veri vermez; Binance (kripto) verir. Sondan
+        // index'e göre hizalama, farklı takvim günlerini eşleştirip yanlışlıkla
+        // "anlaşma" görünümü verirdi; tarihe göre hizalama bunu önler.
+        let av = vec![
+            (
+                "2026-01-02".to_string(),
+                Candle {
+                    open: 100.0,
+                    high: 100.0,
+                    low: 100.0,
+                    close: 100.0,
+                },
+            ),
+            (
+                "2026-01-05".to_string(),
+                Candle {
+                    open: 101.0,
+                    high: 101.0,
+                    low: 101.0,
+                    close: 101.0,
+                },
+            ),
+        ];
+        let crypto = vec![
+            (
+                "2026-01-03".to_string(),
+                Candle {
+                    open: 50.0,
+                    high: 50.0,
+                    low: 50.0,
+                    close: 50.0,
+                },
+            ),
+            (
+                "2026-01-05".to_string(),
+                Candle {
+                    open: 101.2,
+                    high: 101.2,
+                    low: 101.2,
+                    close: 101.2,
+                },
+            ),
+        ];
+        let result = aggregate_closes(&[av, crypto], 0.10).unwrap();
+        assert_eq!(
+            result.dates,
+            vec!["2026-01-02", "2026-01-03", "2026-01-05"]
+        );
+        // Sadece son tarihte iki kaynak da veri veriyor ve anlaşıyor.
+        assert_eq!(result.sources_total, 2);
+        assert_eq!(result.sources_agreed, 2);
+    }
+
+    #[test]
+    fn civil_from_days_matches_known_epoch_date() {
+        assert_eq!(epoch_millis_to_date(0), "1970-01-01");
+        assert_eq!(epoch_millis_to_date(1_700_000_000_000), "2023-11-14");
+    }
+}
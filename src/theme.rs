@@ -0,0 +1,66 @@
+use ratatui::style::Color;
+
+/// Arayüzün tamamında kullanılan renk paleti: kenarlıklar, grafik çizgisi,
+/// eksenler, hata metni ve mum gövdeleri için yükseliş/düşüş renkleri.
+pub struct Theme {
+    pub name: &'static str,
+    pub border: Color,
+    pub chart_line: Color,
+    pub overlay_line: Color,
+    pub axis: Color,
+    pub error_text: Color,
+    pub candle_up: Color,
+    pub candle_down: Color,
+}
+
+pub const THEMES: &[Theme] = &[
+    Theme {
+        name: "Koyu",
+        border: Color::Cyan,
+        chart_line: Color::Yellow,
+        overlay_line: Color::Magenta,
+        axis: Color::Gray,
+        error_text: Color::Red,
+        candle_up: Color::Green,
+        candle_down: Color::Red,
+    },
+    Theme {
+        name: "Açık",
+        border: Color::Blue,
+        chart_line: Color::Black,
+        overlay_line: Color::Rgb(120, 60, 0),
+        axis: Color::DarkGray,
+        error_text: Color::Red,
+        candle_up: Color::Rgb(0, 110, 0),
+        candle_down: Color::Rgb(160, 0, 0),
+    },
+    Theme {
+        name: "Yüksek Kontrast",
+        border: Color::White,
+        chart_line: Color::White,
+        overlay_line: Color::LightYellow,
+        axis: Color::White,
+        error_text: Color::LightRed,
+        candle_up: Color::LightGreen,
+        candle_down: Color::LightRed,
+    },
+];
+
+/// Çalışma anında seçilen temayı taşır; `t` tuşu ile döngüsel olarak değişir.
+pub struct Resources {
+    theme_index: usize,
+}
+
+impl Resources {
+    pub fn new() -> Self {
+        Self { theme_index: 0 }
+    }
+
+    pub fn theme(&self) -> &'static Theme {
+        &THEMES[self.theme_index]
+    }
+
+    pub fn next_theme(&mut self) {
+        self.theme_index = (self.theme_index + 1) % THEMES.len();
+    }
+}
@@ -0,0 +1,192 @@
+use std::time::Instant;
+
+use tokio::sync::{mpsc, watch};
+
+use crate::cache;
+use crate::providers::{Candle, PriceProvider, aggregate_closes, fetch_all_daily, today_date};
+
+/// Worker görevinden render döngüsüne yayılan en güncel durum.
+#[derive(Clone)]
+pub struct PriceUpdate {
+    pub symbol: String,
+    /// `prices` ile aynı indekslerde, kronolojik sırada tarihler.
+    pub dates: Vec<String>,
+    pub prices: Vec<f64>,
+    pub candles: Vec<Candle>,
+    pub sources_agreed: usize,
+    pub sources_total: usize,
+    pub error: Option<String>,
+    pub fetched_at: Instant,
+    pub refreshing: bool,
+    /// Bu anlık görüntü diskteki önbellekten mi geliyor, yoksa ağdan mı.
+    pub from_cache: bool,
+}
+
+impl PriceUpdate {
+    fn empty(symbol: String) -> Self {
+        Self {
+            symbol,
+            dates: Vec::new(),
+            prices: Vec::new(),
+            candles: Vec::new(),
+            sources_agreed: 0,
+            sources_total: 0,
+            error: None,
+            fetched_at: Instant::now(),
+            refreshing: true,
+            from_cache: false,
+        }
+    }
+}
+
+/// Render döngüsünden worker'a gönderilen istekler.
+pub enum WorkerRequest {
+    ChangeSymbol(String),
+    /// Sağlayıcıların döndürdüğü tüm günleri, önbellekteki son tarihten
+    /// bağımsız olarak yeniden indirir ve önbellekteki aynı tarihlerin üzerine
+    /// yazar; bu pencerenin dışındaki eski önbellek kayıtları korunur.
+    Refresh,
+}
+
+/// Ağ çağrılarını yürüten arka plan görevini başlatır; render döngüsü yalnızca
+/// döndürülen `watch::Receiver`'dan en son değeri okur, asla I/O'yu beklemez.
+pub fn spawn(
+    providers: Vec<Box<dyn PriceProvider>>,
+    initial_symbol: String,
+    days: usize,
+) -> (watch::Receiver<PriceUpdate>, mpsc::UnboundedSender<WorkerRequest>) {
+    let (update_tx, update_rx) = watch::channel(PriceUpdate::empty(initial_symbol.clone()));
+    let (req_tx, mut req_rx) = mpsc::unbounded_channel::<WorkerRequest>();
+
+    req_tx
+        .send(WorkerRequest::ChangeSymbol(initial_symbol.clone()))
+        .ok();
+
+    tokio::spawn(async move {
+        let providers = std::sync::Arc::new(providers);
+        let mut symbol = initial_symbol;
+
+        while let Some(req) = req_rx.recv().await {
+            let force_refresh = matches!(req, WorkerRequest::Refresh);
+            if let WorkerRequest::ChangeSymbol(new_symbol) = req {
+                symbol = new_symbol;
+            }
+
+            // Önbellekte bir şey varsa ağ beklenmeden hemen gösterilir.
+            let cached = cache::load(&symbol);
+            if let Some(cached) = &cached {
+                if !cached.closes.is_empty() {
+                    let mut snapshot = update_tx.borrow().clone();
+                    snapshot.symbol = symbol.clone();
+                    snapshot.dates = cached.dates.clone();
+                    snapshot.prices = cached.closes.clone();
+                    snapshot.candles = Vec::new();
+                    snapshot.error = None;
+                    snapshot.fetched_at = Instant::now();
+                    snapshot.refreshing = true;
+                    snapshot.from_cache = true;
+                    update_tx.send_replace(snapshot);
+                }
+            }
+
+            // Önbellek zaten bugüne kadar güncelse ve kullanıcı zorlamıyorsa
+            // AV'nin sınırlı ücretsiz kotasını boşuna harcamamak için ağ
+            // çağrısı hiç yapılmaz; gösterilen anlık görüntü nihai sonuçtur.
+            let is_fresh = !force_refresh
+                && cached
+                    .as_ref()
+                    .and_then(|c| c.latest_date())
+                    .is_some_and(|latest| latest == today_date());
+            if is_fresh {
+                let mut snapshot = update_tx.borrow().clone();
+                snapshot.refreshing = false;
+                update_tx.send_replace(snapshot);
+                continue;
+            }
+
+            let mut in_flight = update_tx.borrow().clone();
+            in_flight.refreshing = true;
+            update_tx.send_replace(in_flight);
+
+            let providers = providers.clone();
+            let fetch_symbol = symbol.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let per_provider = fetch_all_daily(&providers, &fetch_symbol, days);
+                let candles = per_provider
+                    .first()
+                    .map(|series| series.iter().map(|(_, candle)| *candle).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                let aggregated = aggregate_closes(&per_provider, 0.10);
+                (aggregated, candles)
+            })
+            .await;
+
+            let update = match result {
+                Ok((Ok(aggregated), candles)) => {
+                    let latest_known = cached.as_ref().and_then(|c| c.latest_date()).map(|s| s.to_string());
+                    let all_points: Vec<(String, f64)> = aggregated
+                        .dates
+                        .iter()
+                        .cloned()
+                        .zip(aggregated.closes.iter().copied())
+                        .collect();
+                    let fresh: Vec<(String, f64)> = match (&latest_known, force_refresh) {
+                        (_, true) | (None, false) => all_points,
+                        (Some(latest), false) => all_points
+                            .into_iter()
+                            .filter(|(date, _)| date.as_str() > latest.as_str())
+                            .collect(),
+                    };
+                    let mut cached = cached.clone().unwrap_or_default();
+                    cached.merge(&fresh);
+                    cache::save(&symbol, &cached);
+
+                    // Ekranda gösterilen seri, bu taramanın `days` penceresi
+                    // değil, önbelleğe az önce birleştirilen tam birikmiş
+                    // geçmiş olmalı — aksi halde grafik önce tam geçmişi
+                    // (önbellek anlık görüntüsü), sonra ağ sonucu gelince
+                    // aniden 30 güne geri küçülürdü.
+                    PriceUpdate {
+                        symbol: symbol.clone(),
+                        dates: cached.dates.clone(),
+                        prices: cached.closes.clone(),
+                        candles,
+                        sources_agreed: aggregated.sources_agreed,
+                        sources_total: aggregated.sources_total,
+                        error: None,
+                        fetched_at: Instant::now(),
+                        refreshing: false,
+                        from_cache: false,
+                    }
+                }
+                Ok((Err(e), _)) => PriceUpdate {
+                    symbol: symbol.clone(),
+                    dates: update_tx.borrow().dates.clone(),
+                    prices: update_tx.borrow().prices.clone(),
+                    candles: update_tx.borrow().candles.clone(),
+                    sources_agreed: 0,
+                    sources_total: 0,
+                    error: Some(e),
+                    fetched_at: Instant::now(),
+                    refreshing: false,
+                    from_cache: update_tx.borrow().from_cache,
+                },
+                Err(join_err) => PriceUpdate {
+                    symbol: symbol.clone(),
+                    dates: update_tx.borrow().dates.clone(),
+                    prices: update_tx.borrow().prices.clone(),
+                    candles: update_tx.borrow().candles.clone(),
+                    sources_agreed: 0,
+                    sources_total: 0,
+                    error: Some(format!("Worker görevi çöktü: {}", join_err)),
+                    fetched_at: Instant::now(),
+                    refreshing: false,
+                    from_cache: update_tx.borrow().from_cache,
+                },
+            };
+            update_tx.send(update).ok();
+        }
+    });
+
+    (update_rx, req_tx)
+}